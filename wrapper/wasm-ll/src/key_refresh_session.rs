@@ -0,0 +1,21 @@
+// Copyright (c) Silence Laboratories Pte. Ltd. All Rights Reserved.
+// This software is licensed under the Silence Laboratories License Agreement.
+
+// `KeyRefreshSession` (a WASM wrapper for a proactive resharing /
+// key-refresh session, analogous to `SignSessionOTVariant`) is
+// intentionally NOT implemented in this file.
+//
+// A prior version of this file shipped a guessed API surface —
+// `dkls23_ll::key_refresh::{State, RefreshMsg1, RefreshMsg2,
+// KeyRefreshError}` with `State::new`/`generate_msg1`/`handle_msg1`/
+// `handle_msg2` — without any way to confirm those types, method
+// names, or message shapes against the real `dkls23_ll` crate: no
+// vendored copy of `dkls23_ll` is available in this environment to
+// check against. Shipping that guess risked landing bindings to an
+// API that doesn't exist.
+//
+// Before implementing this wrapper, confirm the exact shape of the
+// crate's key-refresh module (type names, round count, message
+// fields) against the real `dkls23_ll` source, then build the session
+// the same way `SignSessionOTVariant` wraps `dsg_ot_variant` in
+// `sign_ot_variant.rs`.
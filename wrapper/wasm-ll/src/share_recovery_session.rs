@@ -0,0 +1,25 @@
+// Copyright (c) Silence Laboratories Pte. Ltd. All Rights Reserved.
+// This software is licensed under the Silence Laboratories License Agreement.
+
+// `ShareRecoverySession` (a WASM wrapper for recovering a lost
+// participant's keyshare with the help of a threshold subset of the
+// remaining parties) is intentionally NOT implemented in this file.
+//
+// A prior version of this file shipped a guessed API surface —
+// `dkls23_ll::share_recovery::{State, RecoveryMsg1}` with
+// `State::new(rng, Option<Keyshare>, lost_party_id)` and
+// `handle_msg1` — and a module doc comment paraphrased from the
+// feature request rather than grounded in the real protocol code.
+// None of it could be checked against the actual `dkls23_ll` crate:
+// no vendored copy of `dkls23_ll` is available in this environment,
+// so the Lagrange-weighting, pairwise-masking, and public-key-
+// commitment-verification details were never confirmed against real
+// behavior. Shipping that guess risked landing bindings to an API
+// that doesn't exist.
+//
+// Before implementing this wrapper, confirm the exact shape of the
+// crate's share-recovery module (type names, message fields, how
+// helper vs. recovering-party roles are distinguished) against the
+// real `dkls23_ll` source, then build the session the same way
+// `SignSessionOTVariant` wraps `dsg_ot_variant` in
+// `sign_ot_variant.rs`.
@@ -24,12 +24,55 @@ enum Round {
     WaitMsg1,
     WaitMsg2,
     WaitMsg3,
-    Pre(dsg::PreSignature),
-    WaitMsg4(dsg::PartialSignature),
-    Failed,
+    Pre(Vec<dsg::PreSignature>),
+    WaitMsg4(Vec<dsg::PartialSignature>),
+    Failed(Failure),
     Finished,
 }
 
+/// Blame information recorded when the signing state machine aborts,
+/// so a coordinator can identify and exclude the misbehaving party
+/// instead of discarding the whole signing session.
+#[derive(Serialize, Deserialize)]
+struct Failure {
+    /// Ids of the parties blamed for the abort.
+    culprits: Vec<u8>,
+    /// The round in which the failure occurred (1-4).
+    round: u8,
+}
+
+/// Extract the party ids implicated by a signing error, from the
+/// `from_id`/`to_id` carried by the underlying message.
+fn culprits_of(err: &dsg_ot_variant::SignOTVariantError) -> Vec<u8> {
+    let mut culprits = vec![err.from_id];
+    if let Some(to_id) = err.to_id {
+        culprits.push(to_id);
+    }
+
+    culprits
+}
+
+/// `lastMessageBatch` requires consuming the whole presignature pool
+/// in one call: this reports whether `pool_len` and `hash_count`
+/// diverge, so the mismatch can be rejected with an explicit message
+/// instead of an opaque "invalid state".
+fn batch_size_mismatch(pool_len: usize, hash_count: usize) -> bool {
+    pool_len != hash_count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_size_mismatch_detects_divergence() {
+        assert!(batch_size_mismatch(2, 3));
+        assert!(batch_size_mismatch(3, 2));
+        assert!(!batch_size_mismatch(3, 3));
+        assert!(!batch_size_mismatch(0, 0));
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 #[wasm_bindgen]
 pub struct SignSessionOTVariant {
@@ -80,15 +123,127 @@ impl SignSessionOTVariant {
         ciborium::from_reader(bytes).expect_throw("CBOR decode error")
     }
 
+    /// Create a session that starts directly from a pre-signature
+    /// produced by a previous session's `exportPresignature()`.
+    ///
+    /// This lets the three interactive rounds be run ahead of time
+    /// (the offline phase), with only `lastMessage`/`combine` (the
+    /// online, message-dependent phase) left to run here.
+    #[wasm_bindgen(js_name = importPresignature)]
+    pub fn import_presignature(
+        keyshare: Keyshare,
+        chain_path: &str,
+        bytes: &[u8],
+        seed: Option<Vec<u8>>,
+    ) -> Self {
+        let mut rng = maybe_seeded_rng(seed);
+
+        let chain_path = DerivationPath::from_str(chain_path)
+            .expect_throw("invalid derivation path");
+
+        let state = dsg_ot_variant::State::new(
+            &mut rng,
+            keyshare.into_inner(),
+            &chain_path,
+        )
+        .expect_throw("sign session init");
+
+        let pre: dsg::PreSignature =
+            ciborium::from_reader(bytes).expect_throw("CBOR decode error");
+
+        SignSessionOTVariant {
+            state,
+            round: Round::Pre(vec![pre]),
+        }
+    }
+
+    /// Merge more previously exported pre-signatures into this
+    /// session's pool, so a batch of presignatures accumulated across
+    /// several offline runs can later be consumed together by
+    /// `lastMessageBatch`/`combineBatch`.
+    ///
+    /// The session must already hold a pre-signature, e.g. from
+    /// `importPresignature()` or from running the three interactive
+    /// rounds.
+    #[wasm_bindgen(js_name = importMorePresignatures)]
+    pub fn import_more_presignatures(
+        &mut self,
+        bytes: Vec<Vec<u8>>,
+    ) -> Result<(), Error> {
+        let mut imported = Vec::with_capacity(bytes.len());
+        for bytes in bytes {
+            let pre: dsg::PreSignature = ciborium::from_reader(&bytes[..])
+                .map_err(|_| Error::new("CBOR decode error"))?;
+            imported.push(pre);
+        }
+
+        match &mut self.round {
+            Round::Pre(pres) => {
+                pres.append(&mut imported);
+                Ok(())
+            }
+
+            _ => Err(Error::new("invalid state")),
+        }
+    }
+
+    /// Export a pre-signature held by this session as CBOR-encoded
+    /// bytes, so it can be persisted and later fed to
+    /// `importPresignature()` to finish signing without repeating the
+    /// three interactive rounds.
+    ///
+    /// Each pre-signature is single-use: exporting it removes it from
+    /// the session's pool, and once the pool is empty the session
+    /// moves to `Finished`, so the same nonce material cannot be
+    /// reused across two signatures.
+    #[wasm_bindgen(js_name = exportPresignature)]
+    pub fn export_presignature(&mut self) -> Result<Vec<u8>, Error> {
+        match &mut self.round {
+            Round::Pre(pres) => {
+                let pre = pres.pop().ok_or_else(|| Error::new("invalid state"))?;
+
+                let mut buffer = vec![];
+                ciborium::into_writer(&pre, &mut buffer)
+                    .expect_throw("CBOR encode error");
+
+                if pres.is_empty() {
+                    self.round = Round::Finished;
+                }
+
+                Ok(buffer)
+            }
+
+            _ => Err(Error::new("invalid state")),
+        }
+    }
+
     /// Return an error message, if any.
     #[wasm_bindgen(js_name = error)]
     pub fn error(&self) -> Option<Error> {
         match &self.round {
-            Round::Failed => Some(Error::new("failed")),
+            Round::Failed(_) => Some(Error::new("failed")),
             _ => None,
         }
     }
 
+    /// Return the ids of the parties blamed for the failure, if the
+    /// session has failed. Empty otherwise.
+    ///
+    /// A coordinator can use this to exclude the culprits and retry
+    /// signing with the remaining parties, rather than aborting the
+    /// whole quorum.
+    #[wasm_bindgen(js_name = culprits)]
+    pub fn culprits(&self) -> Array {
+        match &self.round {
+            Round::Failed(failure) => failure
+                .culprits
+                .iter()
+                .map(|id| JsValue::from(*id))
+                .collect(),
+            _ => Array::new(),
+        }
+    }
+
     /// Create a fist message and change session state from Init to WaitMg1.
     #[wasm_bindgen(js_name = createFirstMessage)]
     pub fn create_first_message(&mut self) -> Result<Message, Error> {
@@ -107,6 +262,7 @@ impl SignSessionOTVariant {
         msgs: Vec<Message>,
         mut h: H,
         next: Round,
+        round_no: u8,
     ) -> Result<Vec<Message>, Error>
     where
         T: DeserializeOwned,
@@ -126,7 +282,11 @@ impl SignSessionOTVariant {
             }
 
             Err(err) => {
-                self.round = Round::Failed;
+                let culprits = culprits_of(&err);
+                self.round = Round::Failed(Failure {
+                    culprits,
+                    round: round_no,
+                });
                 Err(sign_ot_variant_error(err))
             }
         }
@@ -147,27 +307,36 @@ impl SignSessionOTVariant {
                 msgs,
                 |state, msgs| state.handle_msg1(&mut rng, msgs),
                 Round::WaitMsg2,
+                1,
             ),
 
             Round::WaitMsg2 => self.handle(
                 msgs,
                 |state, msgs| state.handle_msg2(&mut rng, msgs),
                 Round::WaitMsg3,
+                2,
             ),
 
             Round::WaitMsg3 => {
                 let msgs = Message::decode_vector(&msgs);
-                let pre = self
-                    .state
-                    .handle_msg3(msgs)
-                    .map_err(sign_ot_variant_error)?;
-
-                self.round = Round::Pre(pre);
-
-                Ok(vec![])
+                match self.state.handle_msg3(msgs) {
+                    Ok(pre) => {
+                        self.round = Round::Pre(vec![pre]);
+                        Ok(vec![])
+                    }
+
+                    Err(err) => {
+                        let culprits = culprits_of(&err);
+                        self.round = Round::Failed(Failure {
+                            culprits,
+                            round: 3,
+                        });
+                        Err(sign_ot_variant_error(err))
+                    }
+                }
             }
 
-            Round::Failed => Err(Error::new("failed")),
+            Round::Failed(_) => Err(Error::new("failed")),
 
             _ => Err(Error::new("invalid session state")),
         }
@@ -185,12 +354,13 @@ impl SignSessionOTVariant {
         }
 
         match core::mem::replace(&mut self.round, Round::Finished) {
-            Round::Pre(pre) => {
+            Round::Pre(mut pres) if pres.len() == 1 => {
+                let pre = pres.pop().unwrap();
                 let hash = message_hash.try_into().unwrap();
                 let (partial, msg4) =
                     dsg::create_partial_signature(pre, hash);
 
-                self.round = Round::WaitMsg4(partial);
+                self.round = Round::WaitMsg4(vec![partial]);
 
                 Ok(Message::new(msg4))
             }
@@ -202,34 +372,156 @@ impl SignSessionOTVariant {
         }
     }
 
+    /// Batched variant of `lastMessage`. Derives one partial
+    /// signature per hash, consuming one pre-signature from the pool
+    /// per hash, and returns the messages to send to the other
+    /// parties in the same order as `hashes`.
+    ///
+    /// `hashes` must contain exactly one hash per pre-signature
+    /// currently in the pool: this call always drains the whole pool
+    /// in one step. Use `importMorePresignatures` beforehand to grow
+    /// the pool to the batch size you need, or call `lastMessage`
+    /// instead for a single hash.
+    #[wasm_bindgen(js_name = lastMessageBatch)]
+    pub fn last_message_batch(
+        &mut self,
+        hashes: Vec<Uint8Array>,
+    ) -> Result<Vec<Message>, Error> {
+        if hashes.iter().any(|hash| hash.length() != 32) {
+            return Err(Error::new("invalid message hash"));
+        }
+
+        match core::mem::replace(&mut self.round, Round::Finished) {
+            Round::Pre(pres) if batch_size_mismatch(pres.len(), hashes.len()) => {
+                let pool_len = pres.len();
+                self.round = Round::Pre(pres);
+                Err(Error::new(&format!(
+                    "lastMessageBatch requires exactly one hash per pooled pre-signature: pool holds {pool_len}, got {} hashes",
+                    hashes.len()
+                )))
+            }
+
+            Round::Pre(pres) => {
+                let (partials, out): (Vec<_>, Vec<_>) = pres
+                    .into_iter()
+                    .zip(hashes)
+                    .map(|(pre, hash)| {
+                        let hash: [u8; 32] =
+                            hash.to_vec().try_into().unwrap();
+                        let (partial, msg4) =
+                            dsg::create_partial_signature(pre, hash);
+
+                        (partial, Message::new(msg4))
+                    })
+                    .unzip();
+
+                self.round = Round::WaitMsg4(partials);
+
+                Ok(out)
+            }
+
+            prev => {
+                self.round = prev;
+                Err(Error::new("invalid state"))
+            }
+        }
+    }
+
     /// Combine last messages and return signature as [R, S].
     /// R, S are 32 byte UintArray.
     ///
-    /// This method consumes the session and deallocates all
-    /// internal data.
-    ///
+    /// On success the session moves to `Finished` and is no longer
+    /// usable. On failure it moves to `Failed` (round 4) with blame
+    /// information still queryable via `culprits()`.
     #[wasm_bindgen(js_name = combine)]
     pub fn combine_partial_signature(
-        self,
+        &mut self,
         msgs: Vec<Message>,
     ) -> Result<Array, Error> {
-        match self.round {
-            Round::WaitMsg4(partial) => {
+        match core::mem::replace(&mut self.round, Round::Finished) {
+            Round::WaitMsg4(mut partials) if partials.len() == 1 => {
+                let partial = partials.pop().unwrap();
                 let msgs = Message::decode_vector(&msgs);
-                let sign = dsg_ot_variant::combine_signatures(partial, msgs)
-                    .map_err(sign_ot_variant_error)?;
-
-                let (r, s) = sign.split_bytes();
+                match dsg_ot_variant::combine_signatures(partial, msgs) {
+                    Ok(sign) => {
+                        let (r, s) = sign.split_bytes();
+
+                        let a = js_sys::Array::new_with_length(2);
+
+                        a.set(0, Uint8Array::from(&r as &[u8]).into());
+                        a.set(1, Uint8Array::from(&s as &[u8]).into());
+
+                        Ok(a)
+                    }
+
+                    Err(err) => {
+                        let culprits = culprits_of(&err);
+                        self.round = Round::Failed(Failure {
+                            culprits,
+                            round: 4,
+                        });
+                        Err(sign_ot_variant_error(err))
+                    }
+                }
+            }
 
-                let a = js_sys::Array::new_with_length(2);
+            prev => {
+                self.round = prev;
+                Err(Error::new("invalid state"))
+            }
+        }
+    }
 
-                a.set(0, Uint8Array::from(&r as &[u8]).into());
-                a.set(1, Uint8Array::from(&s as &[u8]).into());
+    /// Batched variant of `combine`. Combines the final messages for
+    /// each hash against its corresponding partial signature, in the
+    /// same order as `lastMessageBatch`, and returns an array of
+    /// `[R, S]` pairs.
+    ///
+    /// On success the session moves to `Finished` and is no longer
+    /// usable. On failure it moves to `Failed` (round 4) with blame
+    /// information still queryable via `culprits()`.
+    #[wasm_bindgen(js_name = combineBatch)]
+    pub fn combine_batch(
+        &mut self,
+        msgs: Vec<Vec<Message>>,
+    ) -> Result<Array, Error> {
+        match core::mem::replace(&mut self.round, Round::Finished) {
+            Round::WaitMsg4(partials) if partials.len() == msgs.len() => {
+                let out = Array::new_with_length(partials.len() as u32);
+
+                for (i, (partial, msgs)) in
+                    partials.into_iter().zip(msgs).enumerate()
+                {
+                    let msgs = Message::decode_vector(&msgs);
+                    match dsg_ot_variant::combine_signatures(partial, msgs) {
+                        Ok(sign) => {
+                            let (r, s) = sign.split_bytes();
+
+                            let pair = js_sys::Array::new_with_length(2);
+                            pair.set(0, Uint8Array::from(&r as &[u8]).into());
+                            pair.set(1, Uint8Array::from(&s as &[u8]).into());
+
+                            out.set(i as u32, pair.into());
+                        }
+
+                        Err(err) => {
+                            let culprits = culprits_of(&err);
+                            self.round = Round::Failed(Failure {
+                                culprits,
+                                round: 4,
+                            });
+                            return Err(sign_ot_variant_error(err));
+                        }
+                    }
+                }
 
-                Ok(a)
+                Ok(out)
             }
 
-            _ => Err(Error::new("invalid state")),
+            prev => {
+                self.round = prev;
+                Err(Error::new("invalid state"))
+            }
         }
     }
 }